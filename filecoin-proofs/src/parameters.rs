@@ -1,35 +1,77 @@
 use anyhow::{ensure, Result};
 use sha2::{Digest, Sha256};
+use storage_proofs::crypto::derive_non_interactive_porep_seed;
+use storage_proofs::crypto::xor;
+use storage_proofs::hasher::Hasher;
 use storage_proofs::porep::stacked::{self, LayerChallenges, StackedDrg};
 use storage_proofs::post::fallback;
+use storage_proofs::post::rational::{self, RationalPoSt};
 use storage_proofs::proof::ProofScheme;
 
 use crate::constants::*;
-use crate::types::{MerkleTreeTrait, PaddedBytesAmount, PoStConfig};
+use crate::types::{
+    ApiFeature, ApiVersion, Challenges, Commitment, MerkleTreeTrait, NonInteractiveSealCommitPhase1Output,
+    PaddedBytesAmount, PoRepConfig, PoStConfig, PoStType, SealCommitPhase1Output,
+    SealPreCommitPhase1Output, SectorUpdateConfig, SectorUpdateOutput, SynthProofVault, Ticket,
+    VanillaSealProof,
+};
 
 const DRG_NONCE: [u8; 32] = [
     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
     26, 27, 28, 30, 30, 31,
 ];
 
+/// Domain separation tag for deriving synthetic challenge positions from a replica id.
+const SYNTHETIC_POREP_CHALLENGE_DST: &[u8] = b"filecoin.io/synthetic-porep/challenge";
+
+/// Multiplier giving the synthetic challenge pool headroom over the real per-partition
+/// challenge count, since the pool is fixed before the seed-derived subset is known.
+const SYNTHETIC_POREP_POOL_MULTIPLIER: usize = 32;
+
+/// Domain separation tag for deriving the non-interactive commit-phase seed.
+const NON_INTERACTIVE_POREP_SEED_DST: &str = "filecoin.io/non-interactive-porep/seed";
+
+/// Cap on the per-partition challenge count non-interactive PoRep raises to, compensating
+/// for the soundness lost by removing the chain-randomness round trip.
+const MAX_CHALLENGES_PER_PARTITION: usize = 16;
+
+/// Multiplier applied to the interactive minimum challenge count to approximate the
+/// non-interactive minimum challenge table, before [`MAX_CHALLENGES_PER_PARTITION`] caps it.
+const NON_INTERACTIVE_POREP_CHALLENGE_MULTIPLIER: usize = 8;
+
 type WinningPostSetupParams = fallback::SetupParams;
 pub type WinningPostPublicParams = fallback::PublicParams;
 
 type WindowPostSetupParams = fallback::SetupParams;
 pub type WindowPostPublicParams = fallback::PublicParams;
 
+type RationalPostSetupParams = rational::SetupParams;
+pub type RationalPostPublicParams = rational::PublicParams;
+
 pub fn public_params<Tree: 'static + MerkleTreeTrait>(
     sector_bytes: PaddedBytesAmount,
     partitions: usize,
     porep_id: [u8; 32],
+    api_version: ApiVersion,
+    api_features: &[ApiFeature],
 ) -> Result<stacked::PublicParams<Tree>> {
     StackedDrg::<Tree, DefaultPieceHasher>::setup(&setup_params(
         sector_bytes,
         partitions,
         porep_id,
+        api_version,
+        api_features,
     )?)
 }
 
+/// [`public_params`], reading `porep_id`, `partitions`, `api_version`, and `api_features`
+/// from `porep_config` rather than taking them as separate arguments.
+pub fn public_params_for_config<Tree: 'static + MerkleTreeTrait>(
+    porep_config: &PoRepConfig,
+) -> Result<stacked::PublicParams<Tree>> {
+    StackedDrg::<Tree, DefaultPieceHasher>::setup(&setup_params_for_config(porep_config)?)
+}
+
 pub fn winning_post_public_params<Tree: 'static + MerkleTreeTrait>(
     post_config: &PoStConfig,
 ) -> Result<WinningPostPublicParams> {
@@ -57,6 +99,7 @@ pub fn winning_post_setup_params(post_config: &PoStConfig) -> Result<WinningPost
         sector_size: post_config.padded_sector_size().into(),
         challenge_count: param_challenge_count,
         sector_count: param_sector_count,
+        api_version: post_config.api_version,
     })
 }
 
@@ -71,9 +114,144 @@ pub fn window_post_setup_params(post_config: &PoStConfig) -> WindowPostSetupPara
         sector_size: post_config.padded_sector_size().into(),
         challenge_count: post_config.challenge_count,
         sector_count: post_config.sector_count,
+        api_version: post_config.api_version,
     }
 }
 
+/// Unlike [`winning_post_public_params`]/[`window_post_public_params`], which challenge a
+/// fixed-size sector group, Rational PoSt challenges proportionally over however many
+/// sectors are currently live, so its public params carry no `sector_count`.
+pub fn rational_post_public_params(post_config: &PoStConfig) -> Result<RationalPostPublicParams> {
+    Ok(RationalPoSt::setup(&rational_post_setup_params(
+        &post_config,
+    )?))
+}
+
+pub fn rational_post_setup_params(post_config: &PoStConfig) -> Result<RationalPostSetupParams> {
+    Ok(rational::SetupParams {
+        sector_size: post_config.padded_sector_size().into(),
+        challenges_count: post_config.challenge_count,
+    })
+}
+
+/// Public params for whichever PoSt scheme `post_config.typ` selects, so a caller holding
+/// only a [`PoStConfig`] doesn't need to know ahead of time which of
+/// [`winning_post_public_params`]/[`window_post_public_params`]/[`rational_post_public_params`]
+/// applies.
+pub enum PoStPublicParams {
+    Winning(WinningPostPublicParams),
+    Window(WindowPostPublicParams),
+    Rational(RationalPostPublicParams),
+}
+
+pub fn post_public_params<Tree: 'static + MerkleTreeTrait>(
+    post_config: &PoStConfig,
+) -> Result<PoStPublicParams> {
+    match post_config.typ {
+        PoStType::Winning => Ok(PoStPublicParams::Winning(winning_post_public_params::<Tree>(
+            post_config,
+        )?)),
+        PoStType::Window => Ok(PoStPublicParams::Window(window_post_public_params::<Tree>(
+            post_config,
+        )?)),
+        PoStType::Rational => Ok(PoStPublicParams::Rational(rational_post_public_params(
+            post_config,
+        )?)),
+    }
+}
+
+/// Setup params for an Empty Sector Update proof, mirroring [`setup_params`]'s shape for
+/// the update circuit (`storage_proofs::update::EmptySectorUpdate`).
+#[derive(Clone, Debug)]
+pub struct SectorUpdateSetupParams {
+    pub nodes_count: usize,
+    pub update_partitions: usize,
+}
+
+pub type SectorUpdatePublicParams = SectorUpdateSetupParams;
+
+pub fn sector_update_setup_params(config: &SectorUpdateConfig) -> SectorUpdateSetupParams {
+    SectorUpdateSetupParams {
+        nodes_count: config.nodes_count,
+        update_partitions: config.update_partitions.into(),
+    }
+}
+
+pub fn sector_update_public_params(config: &SectorUpdateConfig) -> Result<SectorUpdatePublicParams> {
+    Ok(sector_update_setup_params(config))
+}
+
+/// Domain separation tag for deriving `comm_r_new` in an Empty Sector Update.
+const SECTOR_UPDATE_COMM_R_DST: &[u8] = b"filecoin.io/sector-update/comm-r-new";
+
+fn derive_sector_update_comm_r_new(
+    comm_r_old: Commitment,
+    comm_d_new: Commitment,
+    delta: &[u8],
+) -> Commitment {
+    let hash = Sha256::new()
+        .chain(SECTOR_UPDATE_COMM_R_DST)
+        .chain(comm_r_old)
+        .chain(comm_d_new)
+        .chain(delta)
+        .result();
+
+    let mut comm_r_new = [0u8; 32];
+    comm_r_new.copy_from_slice(&hash[..32]);
+    comm_r_new
+}
+
+/// Replaces `old_replica` with `new_data` for an Empty Sector Update: the delta is carried
+/// as `xor::encode(old_replica, new_data)`, and `comm_r_new` is derived from `comm_r_old`,
+/// `comm_d_new` and that delta so it can later be checked by [`verify_sector_update`]
+/// instead of trusted outright.
+pub fn encode_sector_update(
+    comm_r_old: Commitment,
+    comm_d_new: Commitment,
+    old_replica: &[u8],
+    new_data: &[u8],
+) -> Result<SectorUpdateOutput> {
+    ensure!(
+        old_replica.len() == new_data.len(),
+        "old replica and new data must be the same length, got {} and {}",
+        old_replica.len(),
+        new_data.len()
+    );
+
+    let delta = xor::encode(old_replica, new_data);
+    let comm_r_new = derive_sector_update_comm_r_new(comm_r_old, comm_d_new, &delta);
+
+    Ok(SectorUpdateOutput {
+        comm_r_old,
+        comm_r_new,
+        comm_d_new,
+    })
+}
+
+/// Verification-side counterpart to [`encode_sector_update`]: recovers the new data from
+/// `old_replica` and `delta`, then re-derives `comm_r_new` the same way rather than
+/// trusting `output.comm_r_new`.
+pub fn verify_sector_update(
+    output: &SectorUpdateOutput,
+    old_replica: &[u8],
+    delta: &[u8],
+) -> Result<Vec<u8>> {
+    ensure!(
+        old_replica.len() == delta.len(),
+        "old replica and delta must be the same length, got {} and {}",
+        old_replica.len(),
+        delta.len()
+    );
+
+    let expected = derive_sector_update_comm_r_new(output.comm_r_old, output.comm_d_new, delta);
+    ensure!(
+        expected == output.comm_r_new,
+        "comm_r_new does not match the one derived from comm_r_old, comm_d_new, and delta"
+    );
+
+    Ok(xor::decode(old_replica, delta))
+}
+
 fn drg_seed_from_porep_id(porep_id: [u8; 32]) -> [u8; 28] {
     let mut drg_seed = [0; 28];
 
@@ -87,20 +265,11 @@ pub fn setup_params(
     sector_bytes: PaddedBytesAmount,
     partitions: usize,
     porep_id: [u8; 32],
+    api_version: ApiVersion,
+    api_features: &[ApiFeature],
 ) -> Result<stacked::SetupParams> {
-    let layer_challenges = select_challenges(
-        partitions,
-        *POREP_MINIMUM_CHALLENGES
-            .read()
-            .unwrap()
-            .get(&u64::from(sector_bytes))
-            .expect("unknown sector size") as usize,
-        *LAYERS
-            .read()
-            .unwrap()
-            .get(&u64::from(sector_bytes))
-            .expect("unknown sector size"),
-    )?;
+    let challenges = porep_challenges(sector_bytes, partitions, api_features)?;
+    let layer_challenges = challenges.layer_challenges().clone();
     let sector_bytes = u64::from(sector_bytes);
 
     ensure!(
@@ -121,9 +290,71 @@ pub fn setup_params(
         expansion_degree,
         seed: drg_seed,
         layer_challenges,
+        api_version,
     })
 }
 
+/// [`setup_params`], reading `porep_id`, `partitions`, `api_version`, and `api_features`
+/// from `porep_config` rather than taking them as separate arguments.
+pub fn setup_params_for_config(porep_config: &PoRepConfig) -> Result<stacked::SetupParams> {
+    setup_params(
+        porep_config.padded_bytes_amount(),
+        usize::from(porep_config.partitions),
+        porep_config.porep_id,
+        porep_config.api_version,
+        &porep_config.api_features,
+    )
+}
+
+/// Decides, per `api_features`, whether a seal uses the interactive or synthetic
+/// challenge-selection path and how many challenges that path needs.
+pub fn porep_challenges(
+    sector_bytes: PaddedBytesAmount,
+    partitions: usize,
+    api_features: &[ApiFeature],
+) -> Result<Challenges> {
+    let minimum_total_challenges = *POREP_MINIMUM_CHALLENGES
+        .read()
+        .unwrap()
+        .get(&u64::from(sector_bytes))
+        .expect("unknown sector size") as usize;
+    let layers = *LAYERS
+        .read()
+        .unwrap()
+        .get(&u64::from(sector_bytes))
+        .expect("unknown sector size");
+
+    ensure!(
+        !(api_features.contains(&ApiFeature::SyntheticPoRep)
+            && api_features.contains(&ApiFeature::NonInteractivePoRep)),
+        "SyntheticPoRep and NonInteractivePoRep cannot both be active"
+    );
+
+    if api_features.contains(&ApiFeature::SyntheticPoRep) {
+        let layer_challenges = select_challenges(partitions, minimum_total_challenges, layers)?;
+        let pool_size = minimum_total_challenges * SYNTHETIC_POREP_POOL_MULTIPLIER;
+        Ok(Challenges::Synthetic {
+            layer_challenges,
+            pool_size,
+        })
+    } else if api_features.contains(&ApiFeature::NonInteractivePoRep) {
+        let non_interactive_minimum =
+            minimum_total_challenges * NON_INTERACTIVE_POREP_CHALLENGE_MULTIPLIER;
+        let mut layer_challenges =
+            select_challenges(partitions, non_interactive_minimum, layers)?;
+        if layer_challenges.challenges_count_all() > MAX_CHALLENGES_PER_PARTITION {
+            layer_challenges = LayerChallenges::new(layers, MAX_CHALLENGES_PER_PARTITION);
+        }
+        Ok(Challenges::Interactive(layer_challenges))
+    } else {
+        Ok(Challenges::Interactive(select_challenges(
+            partitions,
+            minimum_total_challenges,
+            layers,
+        )?))
+    }
+}
+
 fn select_challenges(
     partitions: usize,
     minimum_total_challenges: usize,
@@ -138,6 +369,211 @@ fn select_challenges(
     Ok(guess)
 }
 
+/// Deterministically derives the synthetic challenge pool for a replica: `pool_size`
+/// node positions, each computed as `SHA256(replica_id || DST || i) mod nodes`.
+pub fn select_synthetic_challenge_positions(
+    replica_id: &[u8],
+    nodes: usize,
+    pool_size: usize,
+) -> Vec<usize> {
+    (0..pool_size)
+        .map(|i| {
+            let digest = Sha256::new()
+                .chain(replica_id)
+                .chain(SYNTHETIC_POREP_CHALLENGE_DST)
+                .chain(&(i as u64).to_le_bytes())
+                .result();
+
+            let mut index_bytes = [0u8; 8];
+            index_bytes.copy_from_slice(&digest[..8]);
+            (u64::from_le_bytes(index_bytes) % nodes as u64) as usize
+        })
+        .collect()
+}
+
+/// Builds the synthetic proof vault for [`SealPreCommitPhase1Output::synth_proofs`]: walks
+/// the tree once per position [`select_synthetic_challenge_positions`] returns, and binds
+/// the resulting openings to `comm_r` so [`synthetic_proofs_for_seed`] can look them up
+/// later instead of re-walking.
+pub fn build_synthetic_proof_vault<Tree: MerkleTreeTrait>(
+    pre_commit: &SealPreCommitPhase1Output<Tree>,
+    replica_id_bytes: &[u8],
+    comm_r: Commitment,
+    nodes: usize,
+    pool_size: usize,
+) -> Result<SynthProofVault<Tree>> {
+    let synthetic_proofs = select_synthetic_challenge_positions(replica_id_bytes, nodes, pool_size)
+        .into_iter()
+        .map(|challenge| {
+            StackedDrg::<Tree, DefaultPieceHasher>::prove_single_challenge(
+                &pre_commit.labels,
+                &pre_commit.config,
+                pre_commit.comm_d,
+                challenge,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(SynthProofVault {
+        comm_r,
+        synthetic_proofs,
+    })
+}
+
+/// Selects which positions in an already-fixed synthetic pool the commit-phase seed
+/// actually challenges for `partition_index`.
+pub fn select_seed_challenge_indices(
+    seed: &Ticket,
+    pool_size: usize,
+    subset_size: usize,
+    partition_index: u8,
+) -> Vec<usize> {
+    (0..subset_size)
+        .map(|i| {
+            let digest = Sha256::new()
+                .chain(seed)
+                .chain(&[partition_index])
+                .chain(&(i as u64).to_le_bytes())
+                .result();
+
+            let mut index_bytes = [0u8; 8];
+            index_bytes.copy_from_slice(&digest[..8]);
+            (u64::from_le_bytes(index_bytes) % pool_size as u64) as usize
+        })
+        .collect()
+}
+
+/// Looks up the vanilla proofs a commit-phase seed needs for `partition_index` from a
+/// synthetic proof vault produced at PreCommit time, instead of re-walking the tree.
+pub fn synthetic_proofs_for_seed<Tree: MerkleTreeTrait>(
+    vault: &SynthProofVault<Tree>,
+    comm_r: Commitment,
+    seed: &Ticket,
+    layer_challenges: &LayerChallenges,
+    partition_index: u8,
+) -> Result<Vec<VanillaSealProof<Tree>>> {
+    ensure!(
+        vault.comm_r == comm_r,
+        "synthetic proof vault is bound to a different comm_r"
+    );
+
+    let subset_size = layer_challenges.challenges_count_all();
+    ensure!(
+        subset_size <= vault.synthetic_proofs.len(),
+        "synthetic proof vault has {} openings, but partition needs {}",
+        vault.synthetic_proofs.len(),
+        subset_size
+    );
+
+    Ok(select_seed_challenge_indices(
+        seed,
+        vault.synthetic_proofs.len(),
+        subset_size,
+        partition_index,
+    )
+    .into_iter()
+    .map(|i| vault.synthetic_proofs[i].clone())
+    .collect())
+}
+
+/// Assembles a [`SealCommitPhase1Output`] for the synthetic path: rather than walking the
+/// tree for `seed`, it looks up the openings `seed` selects out of `pre_commit`'s vault.
+pub fn seal_commit_phase1_from_vault<Tree: MerkleTreeTrait>(
+    pre_commit: &SealPreCommitPhase1Output<Tree>,
+    comm_r: Commitment,
+    replica_id: <Tree::Hasher as Hasher>::Domain,
+    seed: Ticket,
+    ticket: Ticket,
+    partitions: usize,
+    layer_challenges: &LayerChallenges,
+) -> Result<SealCommitPhase1Output<Tree>> {
+    let vault = pre_commit
+        .synth_proofs
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("no synthetic proof vault in this PreCommit output"))?;
+
+    let vanilla_proofs = (0..partitions)
+        .map(|partition_index| {
+            synthetic_proofs_for_seed(vault, comm_r, &seed, layer_challenges, partition_index as u8)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(SealCommitPhase1Output {
+        vanilla_proofs,
+        comm_r,
+        comm_d: pre_commit.comm_d,
+        replica_id,
+        seed,
+        ticket,
+    })
+}
+
+/// Derives the commit-phase seed for non-interactive PoRep from the replica's own
+/// committed state, so the proof can be generated offline with no chain round trip.
+pub fn derive_non_interactive_seed(
+    replica_id: &[u8],
+    comm_r: Commitment,
+    partition_index: u8,
+) -> Ticket {
+    derive_non_interactive_porep_seed(
+        NON_INTERACTIVE_POREP_SEED_DST,
+        replica_id,
+        comm_r,
+        partition_index,
+    )
+}
+
+/// Assembles a [`NonInteractiveSealCommitPhase1Output`], deriving one seed per partition
+/// via [`derive_non_interactive_seed`].
+pub fn seal_commit_phase1_non_interactive<Tree: MerkleTreeTrait>(
+    vanilla_proofs: Vec<Vec<VanillaSealProof<Tree>>>,
+    comm_r: Commitment,
+    comm_d: Commitment,
+    replica_id: <Tree::Hasher as Hasher>::Domain,
+    replica_id_bytes: &[u8],
+    ticket: Ticket,
+) -> NonInteractiveSealCommitPhase1Output<Tree> {
+    let seeds = (0..vanilla_proofs.len())
+        .map(|partition_index| {
+            derive_non_interactive_seed(replica_id_bytes, comm_r, partition_index as u8)
+        })
+        .collect();
+
+    NonInteractiveSealCommitPhase1Output {
+        vanilla_proofs,
+        comm_r,
+        comm_d,
+        replica_id,
+        seeds,
+        ticket,
+    }
+}
+
+/// Verification-side counterpart to [`seal_commit_phase1_non_interactive`]: re-derives each
+/// partition's expected seed rather than trusting `commit.seeds`.
+pub fn verify_non_interactive_seed<Tree: MerkleTreeTrait>(
+    commit: &NonInteractiveSealCommitPhase1Output<Tree>,
+    replica_id_bytes: &[u8],
+) -> Result<()> {
+    ensure!(
+        commit.seeds.len() == commit.vanilla_proofs.len(),
+        "non-interactive PoRep commit has {} seeds for {} partitions",
+        commit.seeds.len(),
+        commit.vanilla_proofs.len()
+    );
+
+    for (partition_index, seed) in commit.seeds.iter().enumerate() {
+        let expected =
+            derive_non_interactive_seed(replica_id_bytes, commit.comm_r, partition_index as u8);
+        ensure!(
+            *seed == expected,
+            "non-interactive PoRep seed for partition {} does not match the one derived from comm_r and replica_id",
+            partition_index
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,6 +603,7 @@ mod tests {
             challenge_count: 66,
             sector_count: 1,
             sector_size: 2048u64.into(),
+            api_version: ApiVersion::V1_1_0,
         };
 
         let params = winning_post_public_params::<DefaultOctLCTree>(&config).unwrap();
@@ -174,4 +611,121 @@ mod tests {
         assert_eq!(params.challenge_count, 1);
         assert_eq!(params.sector_size, 2048);
     }
+
+    #[test]
+    fn synthetic_challenge_positions_are_in_range_and_deterministic() {
+        let replica_id = [7u8; 32];
+        let nodes = 1024;
+        let pool_size = 64;
+
+        let a = select_synthetic_challenge_positions(&replica_id, nodes, pool_size);
+        let b = select_synthetic_challenge_positions(&replica_id, nodes, pool_size);
+
+        assert_eq!(a, b);
+        assert_eq!(a.len(), pool_size);
+        assert!(a.iter().all(|&pos| pos < nodes));
+    }
+
+    #[test]
+    fn synthetic_layer_challenges_match_interactive_test() {
+        let sector_bytes: PaddedBytesAmount = 2048u64.into();
+
+        let interactive = porep_challenges(sector_bytes, 1, &[]).unwrap();
+        let synthetic = porep_challenges(sector_bytes, 1, &[ApiFeature::SyntheticPoRep]).unwrap();
+
+        assert_eq!(
+            interactive.layer_challenges().challenges_count_all(),
+            synthetic.layer_challenges().challenges_count_all(),
+        );
+        assert!(
+            synthetic.synthetic_pool_size().unwrap()
+                > synthetic.layer_challenges().challenges_count_all()
+        );
+    }
+
+    #[test]
+    fn porep_challenges_rejects_synthetic_and_non_interactive_together() {
+        let sector_bytes: PaddedBytesAmount = 2048u64.into();
+
+        let result = porep_challenges(
+            sector_bytes,
+            1,
+            &[ApiFeature::SyntheticPoRep, ApiFeature::NonInteractivePoRep],
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn synthetic_proofs_for_seed_rejects_undersized_pool() {
+        let layer_challenges = porep_challenges(2048u64.into(), 1, &[ApiFeature::SyntheticPoRep])
+            .unwrap()
+            .layer_challenges()
+            .clone();
+
+        let vault = SynthProofVault::<DefaultOctLCTree> {
+            comm_r: [0u8; 32],
+            synthetic_proofs: vec![],
+        };
+
+        let err = synthetic_proofs_for_seed(&vault, [0u8; 32], &[1u8; 32], &layer_challenges, 0)
+            .unwrap_err();
+        assert!(err.to_string().contains("synthetic proof vault"));
+    }
+
+    #[test]
+    fn non_interactive_seed_is_deterministic_and_partition_scoped() {
+        let replica_id = [3u8; 32];
+        let comm_r = [9u8; 32];
+
+        let seed_0 = derive_non_interactive_seed(&replica_id, comm_r, 0);
+        let seed_0_again = derive_non_interactive_seed(&replica_id, comm_r, 0);
+        let seed_1 = derive_non_interactive_seed(&replica_id, comm_r, 1);
+
+        assert_eq!(seed_0, seed_0_again);
+        assert_ne!(seed_0, seed_1);
+    }
+
+    #[test]
+    fn sector_update_round_trips_through_xor_delta() {
+        let comm_r_old = [1u8; 32];
+        let comm_d_new = [2u8; 32];
+        let old_replica = vec![5u8; 64];
+        let new_data = vec![9u8; 64];
+
+        let output =
+            encode_sector_update(comm_r_old, comm_d_new, &old_replica, &new_data).unwrap();
+        let delta = xor::encode(&old_replica, &new_data);
+
+        let recovered = verify_sector_update(&output, &old_replica, &delta).unwrap();
+        assert_eq!(recovered, new_data);
+    }
+
+    #[test]
+    fn sector_update_verify_rejects_mismatched_delta() {
+        let comm_r_old = [1u8; 32];
+        let comm_d_new = [2u8; 32];
+        let old_replica = vec![5u8; 64];
+        let new_data = vec![9u8; 64];
+
+        let output =
+            encode_sector_update(comm_r_old, comm_d_new, &old_replica, &new_data).unwrap();
+        let wrong_delta = xor::encode(&old_replica, &vec![0u8; 64]);
+
+        assert!(verify_sector_update(&output, &old_replica, &wrong_delta).is_err());
+    }
+
+    #[test]
+    fn sector_update_verify_rejects_undersized_delta() {
+        let comm_r_old = [1u8; 32];
+        let comm_d_new = [2u8; 32];
+        let old_replica = vec![5u8; 64];
+        let new_data = vec![9u8; 64];
+
+        let output =
+            encode_sector_update(comm_r_old, comm_d_new, &old_replica, &new_data).unwrap();
+        let short_delta = xor::encode(&old_replica, &new_data)[..32].to_vec();
+
+        assert!(verify_sector_update(&output, &old_replica, &short_delta).is_err());
+    }
 }
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// An opt-in PoRep behavior layered on top of the base sealing pipeline.
+///
+/// Features are orthogonal to [`crate::types::ApiVersion`]: a version selects which
+/// circuit/derivation generation a sector uses, while a feature changes *when* and *how*
+/// challenge openings are produced for that sector. `SyntheticPoRep` and
+/// `NonInteractivePoRep` cannot be active together for the same seal.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ApiFeature {
+    /// Precompute a large pool of challenge openings at `SealPreCommitPhase1` time so
+    /// the interactive Commit phase only has to look them up, instead of re-walking
+    /// the tree once the chain-supplied seed is known.
+    SyntheticPoRep,
+    /// Derive the commit-phase seed deterministically from `comm_r` and the replica id
+    /// instead of taking it from chain randomness, so the proof can be produced offline.
+    NonInteractivePoRep,
+}
@@ -4,6 +4,7 @@ use storage_proofs::porep::stacked;
 
 use crate::constants::*;
 
+mod api_feature;
 mod bytes_amount;
 mod piece_info;
 mod porep_config;
@@ -12,7 +13,10 @@ mod post_config;
 mod post_proof_partitions;
 mod sector_class;
 mod sector_size;
+mod sector_update_config;
+mod update_proof_partitions;
 
+pub use self::api_feature::*;
 pub use self::bytes_amount::*;
 pub use self::piece_info::*;
 pub use self::porep_config::*;
@@ -21,11 +25,14 @@ pub use self::post_config::*;
 pub use self::post_proof_partitions::*;
 pub use self::sector_class::*;
 pub use self::sector_size::*;
+pub use self::sector_update_config::*;
+pub use self::update_proof_partitions::*;
 
 pub type Commitment = [u8; 32];
 pub type ChallengeSeed = [u8; 32];
 pub use stacked::PersistentAux;
 pub use stacked::TemporaryAux;
+pub use storage_proofs::ApiVersion;
 pub type ProverId = [u8; 32];
 pub type Ticket = [u8; 32];
 
@@ -61,6 +68,22 @@ pub struct SealCommitPhase1Output<Tree: MerkleTreeTrait> {
     pub ticket: Ticket,
 }
 
+/// Commit-phase-1 output for the non-interactive PoRep path.
+///
+/// Unlike [`SealCommitPhase1Output`], where one seed legitimately covers every partition
+/// because partition differentiation happens elsewhere, here the seed itself is derived
+/// from `partition_index` (see [`crate::parameters::derive_non_interactive_seed`]), so a
+/// multi-partition seal needs one seed per partition rather than a single shared one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NonInteractiveSealCommitPhase1Output<Tree: MerkleTreeTrait> {
+    pub vanilla_proofs: Vec<Vec<VanillaSealProof<Tree>>>,
+    pub comm_r: Commitment,
+    pub comm_d: Commitment,
+    pub replica_id: <Tree::Hasher as Hasher>::Domain,
+    pub seeds: Vec<Ticket>,
+    pub ticket: Ticket,
+}
+
 #[derive(Clone, Debug)]
 pub struct SealCommitOutput {
     pub proof: Vec<u8>,
@@ -73,4 +96,60 @@ pub struct SealPreCommitPhase1Output<Tree: MerkleTreeTrait> {
     pub labels: Labels<Tree>,
     pub config: StoreConfig,
     pub comm_d: Commitment,
+    /// Present when `ApiFeature::SyntheticPoRep` is active: the precomputed openings for
+    /// the synthetic challenge pool.
+    pub synth_proofs: Option<SynthProofVault<Tree>>,
+}
+
+pub use storage_proofs::porep::stacked::LayerChallenges;
+
+/// How the challenged (layer, node) positions for a seal proof are selected.
+#[derive(Clone, Debug)]
+pub enum Challenges {
+    Interactive(LayerChallenges),
+    /// `layer_challenges` is the real per-partition count a committed proof must carry;
+    /// `pool_size` is the larger pool of positions precomputed at PreCommit time.
+    Synthetic {
+        layer_challenges: LayerChallenges,
+        pool_size: usize,
+    },
+}
+
+impl Challenges {
+    pub fn layer_challenges(&self) -> &LayerChallenges {
+        match self {
+            Challenges::Interactive(layer_challenges)
+            | Challenges::Synthetic { layer_challenges, .. } => layer_challenges,
+        }
+    }
+
+    pub fn is_synthetic(&self) -> bool {
+        matches!(self, Challenges::Synthetic { .. })
+    }
+
+    /// The synthetic pool size, or `None` on the interactive path.
+    pub fn synthetic_pool_size(&self) -> Option<usize> {
+        match self {
+            Challenges::Synthetic { pool_size, .. } => Some(*pool_size),
+            Challenges::Interactive(_) => None,
+        }
+    }
+}
+
+/// The precomputed vanilla inclusion proofs for the synthetic challenge pool, keyed by
+/// `comm_r` so a vault can't be swapped in for a different replica.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SynthProofVault<Tree: MerkleTreeTrait> {
+    pub comm_r: Commitment,
+    pub synthetic_proofs: Vec<VanillaSealProof<Tree>>,
+}
+
+/// Output of an Empty Sector Update proof: `comm_r_new` is proven to correctly derive
+/// from `comm_r_old`, `comm_d_new`, and the XOR-encoded delta between them, so a sealed
+/// CC sector can be re-used for real data without a full re-seal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SectorUpdateOutput {
+    pub comm_r_old: Commitment,
+    pub comm_r_new: Commitment,
+    pub comm_d_new: Commitment,
 }
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ApiFeature, ApiVersion, PaddedBytesAmount, PoRepProofPartitions, SectorSize};
+
+/// Configuration for a seal (PoRep) proof.
+///
+/// `api_version` selects which circuit/derivation generation (see [`ApiVersion`]) the
+/// replica is sealed under, and `api_features` selects which opt-in behaviors (synthetic
+/// or non-interactive challenge selection, see [`ApiFeature`]) are active for it. Both are
+/// carried here, mirroring how [`crate::types::PoStConfig`] carries its own `api_version`;
+/// `crate::parameters::setup_params_for_config` and `public_params_for_config` read them
+/// from here, while `setup_params` and `public_params` take them as separate arguments.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PoRepConfig {
+    pub sector_size: SectorSize,
+    pub partitions: PoRepProofPartitions,
+    pub porep_id: [u8; 32],
+    pub api_version: ApiVersion,
+    pub api_features: Vec<ApiFeature>,
+}
+
+impl PoRepConfig {
+    pub fn padded_bytes_amount(&self) -> PaddedBytesAmount {
+        PaddedBytesAmount::from(self.sector_size)
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ApiVersion, PaddedBytesAmount, SectorSize};
+
+/// Which PoSt scheme a [`PoStConfig`] configures.
+///
+/// `Winning` is run once per eligible sector at block-proposal time and `Window` proves
+/// every live sector on a fixed reporting window; both challenge a fixed-size sector
+/// group. `Rational` instead challenges proportionally over however many sectors are
+/// currently live, so its public params carry no `sector_count`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PoStType {
+    Winning,
+    Window,
+    Rational,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct PoStConfig {
+    pub typ: PoStType,
+    pub priority: bool,
+    pub challenge_count: usize,
+    pub sector_count: usize,
+    pub sector_size: SectorSize,
+    pub api_version: ApiVersion,
+}
+
+impl PoStConfig {
+    pub fn padded_sector_size(&self) -> PaddedBytesAmount {
+        PaddedBytesAmount::from(self.sector_size)
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{SectorSize, UpdateProofPartitions};
+
+/// Configuration for an Empty Sector Update (SnapDeals) proof: replacing the data already
+/// sealed into a CC sector with real data, and proving that `comm_r_new` correctly derives
+/// from `comm_r_old`, `comm_d_new`, and the XOR-encoded delta, without a full re-seal.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SectorUpdateConfig {
+    pub sector_size: SectorSize,
+    pub nodes_count: usize,
+    pub update_partitions: UpdateProofPartitions,
+}
+
+impl SectorUpdateConfig {
+    pub fn from_sector_size(sector_size: SectorSize, update_partitions: UpdateProofPartitions) -> Self {
+        let nodes_count = (u64::from(sector_size) / 32) as usize;
+
+        SectorUpdateConfig {
+            sector_size,
+            nodes_count,
+            update_partitions,
+        }
+    }
+}
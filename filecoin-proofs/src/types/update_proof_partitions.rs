@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Number of partitions an Empty Sector Update proof is split across, mirroring
+/// [`crate::types::PoRepProofPartitions`] for the update circuit.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpdateProofPartitions(pub u8);
+
+impl From<UpdateProofPartitions> for usize {
+    fn from(partitions: UpdateProofPartitions) -> Self {
+        partitions.0 as usize
+    }
+}
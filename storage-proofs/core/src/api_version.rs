@@ -0,0 +1,38 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{format_err, Error};
+use serde::{Deserialize, Serialize};
+
+/// Identifies which generation of circuit/derivation behavior a proof was produced under.
+///
+/// Sectors sealed before the 1.1.0 upgrade must keep reproducing the original DRG
+/// parent derivation so that already-sealed sectors remain verifiable, while newly
+/// sealed sectors use the corrected derivation. Threading `ApiVersion` through setup
+/// lets a single binary do both without a recompile.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ApiVersion {
+    V1_0_0,
+    V1_1_0,
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiVersion::V1_0_0 => write!(f, "1.0.0"),
+            ApiVersion::V1_1_0 => write!(f, "1.1.0"),
+        }
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1.0.0" => Ok(ApiVersion::V1_0_0),
+            "1.1.0" => Ok(ApiVersion::V1_1_0),
+            _ => Err(format_err!("invalid api version: {}", s)),
+        }
+    }
+}
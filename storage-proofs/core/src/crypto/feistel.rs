@@ -0,0 +1,164 @@
+use sha2::{Digest, Sha256};
+
+use crate::api_version::ApiVersion;
+
+pub type Index = u64;
+
+const FEISTEL_ROUNDS: usize = 3;
+
+/// Bit masks derived from the node count, shared across all permutations of a given tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FeistelPrecomputed {
+    half_mask: Index,
+    half_bits: u32,
+}
+
+/// Precomputes the masks needed to permute an index space of `num_elements` nodes.
+pub fn precompute(num_elements: Index) -> FeistelPrecomputed {
+    let mut next_pow4: Index = 4;
+    let mut log4 = 1;
+    while next_pow4 < num_elements {
+        next_pow4 *= 4;
+        log4 += 1;
+    }
+
+    let half_bits = log4;
+    let half_mask = (1u64 << half_bits) - 1;
+
+    FeistelPrecomputed {
+        half_mask,
+        half_bits,
+    }
+}
+
+fn round_function(key: Index, right: Index, api_version: ApiVersion) -> Index {
+    let digest = Sha256::new()
+        .chain(key.to_le_bytes())
+        .chain(right.to_le_bytes())
+        .result();
+
+    let mut raw_bytes = [0u8; 8];
+    raw_bytes.copy_from_slice(&digest[..8]);
+    let raw = u64::from_le_bytes(raw_bytes);
+
+    match api_version {
+        // The original permutation folded the round function's output through a
+        // 32-bit intermediate, silently discarding entropy once a sector's half-width
+        // exceeds 32 bits. Sectors sealed under V1_0_0 must keep reproducing that
+        // truncation, or their parents (and therefore comm_r) would no longer verify.
+        ApiVersion::V1_0_0 => Index::from(raw as u32),
+        ApiVersion::V1_1_0 => raw,
+    }
+}
+
+/// Runs a single pass of the Feistel network defined by `keys`, forward (`encode`) or
+/// backward (`decode`), over an `index` in the padded `0..2^(2*half_bits)` domain.
+fn feistel_once(
+    index: Index,
+    keys: &[Index],
+    precomputed: FeistelPrecomputed,
+    api_version: ApiVersion,
+    forward: bool,
+) -> Index {
+    let FeistelPrecomputed {
+        half_mask,
+        half_bits,
+    } = precomputed;
+
+    let mut left = index & half_mask;
+    let mut right = index >> half_bits;
+
+    let ordered_keys: Box<dyn Iterator<Item = &Index>> = if forward {
+        Box::new(keys.iter().take(FEISTEL_ROUNDS))
+    } else {
+        Box::new(keys.iter().take(FEISTEL_ROUNDS).rev())
+    };
+
+    for key in ordered_keys {
+        let next_left = right;
+        let next_right = left ^ (round_function(*key, right, api_version) & half_mask);
+        left = next_left;
+        right = next_right;
+    }
+
+    (left << half_bits) | right
+}
+
+/// Runs the Feistel network defined by `keys` forward (`encode`) or backward (`decode`)
+/// over `index`, cycle-walking back into `0..num_elements` since a single [`feistel_once`]
+/// pass can land outside it when `num_elements` isn't a power of 4.
+fn permute(
+    num_elements: Index,
+    index: Index,
+    keys: &[Index],
+    precomputed: FeistelPrecomputed,
+    api_version: ApiVersion,
+    forward: bool,
+) -> Index {
+    let mut out = feistel_once(index, keys, precomputed, api_version, forward);
+    while out >= num_elements {
+        out = feistel_once(out, keys, precomputed, api_version, forward);
+    }
+    out
+}
+
+pub fn encode(
+    num_elements: Index,
+    index: Index,
+    keys: &[Index],
+    precomputed: FeistelPrecomputed,
+    api_version: ApiVersion,
+) -> Index {
+    permute(num_elements, index, keys, precomputed, api_version, true)
+}
+
+pub fn decode(
+    num_elements: Index,
+    index: Index,
+    keys: &[Index],
+    precomputed: FeistelPrecomputed,
+    api_version: ApiVersion,
+) -> Index {
+    permute(num_elements, index, keys, precomputed, api_version, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_round_trip_for_non_power_of_4_num_elements() {
+        // 100 is not a power of 4, which is the common case: DRG parent-space sizes are
+        // arbitrary node counts, not powers of 4.
+        let num_elements = 100;
+        let keys = [1, 2, 3, 4];
+        let precomputed = precompute(num_elements);
+
+        for api_version in [ApiVersion::V1_0_0, ApiVersion::V1_1_0] {
+            for index in 0..num_elements {
+                let encoded = encode(num_elements, index, &keys, precomputed, api_version);
+                assert!(encoded < num_elements);
+
+                let decoded = decode(num_elements, encoded, &keys, precomputed, api_version);
+                assert_eq!(
+                    decoded, index,
+                    "decode(encode(x)) != x for x = {} under {:?}",
+                    index, api_version
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn encode_is_a_bijection_for_non_power_of_4_num_elements() {
+        let num_elements = 100;
+        let keys = [1, 2, 3, 4];
+        let precomputed = precompute(num_elements);
+
+        let mut seen = std::collections::HashSet::new();
+        for index in 0..num_elements {
+            let encoded = encode(num_elements, index, &keys, precomputed, ApiVersion::V1_1_0);
+            assert!(seen.insert(encoded), "collision at encoded = {}", encoded);
+        }
+    }
+}
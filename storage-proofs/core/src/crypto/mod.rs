@@ -12,3 +12,23 @@ pub fn derive_porep_domain_seed(domain_separation_tag: &str, porep_id: [u8; 32])
         .result()
         .into()
 }
+
+/// Derives a non-interactive PoRep commit seed from the replica's own committed state
+/// instead of chain randomness, following the same domain-separated hashing pattern as
+/// [`derive_porep_domain_seed`]. Binding the seed to `comm_r` and `partition_index` means
+/// each partition of each replica gets its own seed, and verification can re-derive it
+/// rather than trust a caller-supplied value.
+pub fn derive_non_interactive_porep_seed(
+    domain_separation_tag: &str,
+    replica_id: &[u8],
+    comm_r: [u8; 32],
+    partition_index: u8,
+) -> [u8; 32] {
+    Sha256::new()
+        .chain(domain_separation_tag)
+        .chain(replica_id)
+        .chain(comm_r)
+        .chain([partition_index])
+        .result()
+        .into()
+}
@@ -0,0 +1,120 @@
+use std::collections::BTreeSet;
+
+use anyhow::{ensure, Result};
+use sha2::{Digest, Sha256};
+
+/// A sector id, ordered so that the combined node space of an [`OrderedSectorSet`] has a
+/// stable, reproducible layout for challenge derivation.
+pub type SectorId = u64;
+
+pub type ChallengeSeed = [u8; 32];
+
+/// The set of currently live (non-faulty) sector ids a Rational PoSt challenges over.
+/// Unlike Winning/Window PoSt's fixed-size sector groups, this set can be any size, and
+/// grows or shrinks as sectors are added to or removed from the provider's proving set.
+pub type OrderedSectorSet = BTreeSet<SectorId>;
+
+#[derive(Debug, Clone)]
+pub struct SetupParams {
+    pub sector_size: u64,
+    pub challenges_count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct PublicParams {
+    pub sector_size: u64,
+    pub challenges_count: usize,
+}
+
+/// A single challenged (sector, leaf) pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenge {
+    pub sector_id: SectorId,
+    pub leaf: u64,
+}
+
+/// Rational PoSt: challenges sectors proportionally over an ordered, variable-size sector
+/// set rather than a fixed per-sector challenge count, so proving cost scales with the
+/// number of live sectors instead of a fixed sector group size.
+pub struct RationalPoSt;
+
+impl RationalPoSt {
+    pub fn setup(setup_params: &SetupParams) -> PublicParams {
+        PublicParams {
+            sector_size: setup_params.sector_size,
+            challenges_count: setup_params.challenges_count,
+        }
+    }
+
+    /// Deterministically derives `challenges_count` (sector, leaf) pairs from `seed` by
+    /// hashing `seed || challenge_index` into an index over the combined node space of
+    /// every live sector. Every sector covers the same `nodes_per_sector` nodes of that
+    /// space, so each live sector is equally likely to be challenged, and a prover cannot
+    /// predict which sector a given challenge will land on without already knowing the
+    /// full live sector set.
+    ///
+    /// Errors if `sectors` is empty: a miner can legitimately have zero live sectors
+    /// between removing one and sealing its replacement, and that must surface as an
+    /// error here rather than panic on the remainder by a zero `total_nodes` an empty set
+    /// would otherwise produce.
+    pub fn derive_challenges(
+        seed: &ChallengeSeed,
+        sectors: &OrderedSectorSet,
+        nodes_per_sector: u64,
+        challenges_count: usize,
+    ) -> Result<Vec<Challenge>> {
+        ensure!(!sectors.is_empty(), "cannot derive challenges over an empty sector set");
+
+        let sectors: Vec<SectorId> = sectors.iter().copied().collect();
+        let total_nodes = nodes_per_sector * sectors.len() as u64;
+
+        Ok((0..challenges_count)
+            .map(|i| {
+                let digest = Sha256::new()
+                    .chain(seed)
+                    .chain((i as u64).to_le_bytes())
+                    .result();
+
+                let mut index_bytes = [0u8; 8];
+                index_bytes.copy_from_slice(&digest[..8]);
+                let combined_index = u64::from_le_bytes(index_bytes) % total_nodes;
+
+                let sector_index = (combined_index / nodes_per_sector) as usize;
+                let leaf = combined_index % nodes_per_sector;
+
+                Challenge {
+                    sector_id: sectors[sector_index],
+                    leaf,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_challenges_is_deterministic_and_in_range() {
+        let seed = [5u8; 32];
+        let sectors: OrderedSectorSet = vec![1, 2, 3].into_iter().collect();
+        let nodes_per_sector = 128;
+
+        let a = RationalPoSt::derive_challenges(&seed, &sectors, nodes_per_sector, 10).unwrap();
+        let b = RationalPoSt::derive_challenges(&seed, &sectors, nodes_per_sector, 10).unwrap();
+
+        assert_eq!(a, b);
+        assert!(a
+            .iter()
+            .all(|c| sectors.contains(&c.sector_id) && c.leaf < nodes_per_sector));
+    }
+
+    #[test]
+    fn derive_challenges_errors_on_empty_sector_set() {
+        let seed = [5u8; 32];
+        let sectors: OrderedSectorSet = OrderedSectorSet::new();
+
+        assert!(RationalPoSt::derive_challenges(&seed, &sectors, 128, 10).is_err());
+    }
+}